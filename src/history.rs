@@ -0,0 +1,70 @@
+//! Opt-in reading history: remembers where you stopped in a given source so a
+//! later run of the same file can pick up where you left off.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A single remembered position within a source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub current_idx: usize,
+    pub wpm: u32,
+}
+
+/// The on-disk history, a map from a stable per-source key to its last position.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    entries: HashMap<String, Entry>,
+}
+
+/// Build a stable key for a file source from its canonical path and a hash of
+/// its contents, so edits to the file don't resume at a now-stale index.
+/// Returns `None` when the path can't be canonicalized (e.g. it went away).
+pub fn source_key(path: &Path, contents: &str) -> Option<String> {
+    let canonical = fs::canonicalize(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(format!("{}#{:016x}", canonical.display(), hasher.finish()))
+}
+
+fn state_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("ferrous_wheel").join("history.json"))
+}
+
+impl History {
+    /// Load the saved history, falling back to an empty one if the file is
+    /// missing or unreadable.
+    pub fn load() -> Self {
+        state_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn record(&mut self, key: String, entry: Entry) {
+        self.entries.insert(key, entry);
+    }
+
+    /// Persist the history, creating the config directory if needed. A missing
+    /// config dir (no `state_path`) is treated as a silent no-op.
+    pub fn save(&self) -> io::Result<()> {
+        let path = match state_path() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
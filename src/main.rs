@@ -1,15 +1,26 @@
 use arboard::Clipboard;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind,
+        KeyModifiers,
+    },
     execute,
     style::{self, Color, Stylize},
     terminal::{self, ClearType},
 };
+mod history;
+
 use std::fs;
 use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "A simple RSVP app in Rust")]
@@ -24,10 +35,136 @@ struct Cli {
     // focus characters
     #[arg(short, long)]
     focus: bool,
+
+    // color palette; `auto` probes the terminal background at startup
+    #[arg(long, value_enum, default_value_t = ThemeArg::Auto)]
+    theme: ThemeArg,
+
+    // start over from the first word, ignoring any saved position
+    #[arg(long)]
+    reset: bool,
+
+    // don't read or write reading history for this run
+    #[arg(long)]
+    no_resume: bool,
+
+    // vary per-word timing by length, punctuation and paragraph breaks
+    #[arg(long)]
+    dynamic: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ThemeArg {
+    Auto,
+    Light,
+    Dark,
+}
+
+/// The two colors the reader draws words with: the focus (ORP) letter and the
+/// surrounding text.
+#[derive(Copy, Clone)]
+struct Theme {
+    text: Color,
+    orp: Color,
+}
+
+impl Theme {
+    fn dark() -> Self {
+        Theme {
+            text: Color::White,
+            orp: Color::Red,
+        }
+    }
+
+    fn light() -> Self {
+        Theme {
+            text: Color::Black,
+            orp: Color::DarkRed,
+        }
+    }
+}
+
+/// Parse a single `rgb:` channel such as `ffff` or `ff` into a 0.0..=1.0 value,
+/// scaling by the number of hex digits the terminal reported.
+#[cfg(unix)]
+fn parse_channel(s: &str) -> Option<f64> {
+    if s.is_empty() || s.len() > 8 {
+        return None;
+    }
+    let value = u32::from_str_radix(s, 16).ok()?;
+    let max = (1u64 << (4 * s.len())) - 1;
+    Some(value as f64 / max as f64)
+}
+
+/// Pull a perceived-luminance value out of an OSC 11 reply of the form
+/// `\x1b]11;rgb:rrrr/gggg/bbbb\x07`.
+#[cfg(unix)]
+fn parse_osc11_luminance(bytes: &[u8]) -> Option<f64> {
+    let reply = std::str::from_utf8(bytes).ok()?;
+    let rgb = &reply[reply.find("rgb:")? + 4..];
+    let spec: String = rgb
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == '/')
+        .collect();
+    let mut parts = spec.split('/');
+    let r = parse_channel(parts.next()?)?;
+    let g = parse_channel(parts.next()?)?;
+    let b = parse_channel(parts.next()?)?;
+    Some(0.299 * r + 0.587 * g + 0.114 * b)
+}
+
+/// Ask the terminal for its background color via OSC 11 and return its perceived
+/// luminance, giving up after `timeout` if nothing legible comes back. Must be
+/// called with raw mode already enabled so the reply isn't line-buffered.
+///
+/// The reply is read on the calling thread with a `poll()`-bounded read on
+/// stdin, so a terminal that never answers leaves no orphan reader racing
+/// crossterm for the user's first keypress.
+///
+/// Only available on Unix, where we can `poll()` stdin's fd; other targets fall
+/// back to `None` so theme auto-detection uses the dark palette.
+#[cfg(unix)]
+fn query_background_luminance(timeout: Duration) -> Option<f64> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x1b]11;?\x07").ok()?;
+    stdout.flush().ok()?;
+
+    let fd = io::stdin().as_raw_fd();
+    let start = Instant::now();
+    let mut buf = [0u8; 64];
+    let mut reply = Vec::new();
+    while let Some(remaining) = timeout.checked_sub(start.elapsed()) {
+        let mut pollfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ms = remaining.as_millis().min(i32::MAX as u128) as i32;
+        if unsafe { libc::poll(&mut pollfd, 1, ms) } <= 0 {
+            break;
+        }
+        let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if n <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..n as usize]);
+        // Stop as soon as the reply terminator (BEL or ST) arrives.
+        if reply.contains(&0x07) || reply.windows(2).any(|w| w == b"\x1b\\") {
+            break;
+        }
+    }
+    parse_osc11_luminance(&reply)
 }
 
-fn get_orp_index(len: usize) -> usize {
-    match len {
+#[cfg(not(unix))]
+fn query_background_luminance(_timeout: Duration) -> Option<f64> {
+    None
+}
+
+fn get_orp_index(graphemes: usize) -> usize {
+    match graphemes {
         0..=1 => 0,
         2..=5 => 1,
         6..=9 => 2,
@@ -36,10 +173,164 @@ fn get_orp_index(len: usize) -> usize {
     }
 }
 
+/// Owns the terminal's raw/alternate-screen state and restores it on drop, so
+/// the user's terminal is left clean even if the main loop returns early, errors
+/// out, or panics.
+struct TerminalGuard {
+    stdout: io::Stdout,
+}
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            EnableBracketedPaste,
+        )?;
+        Ok(Self { stdout })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // Best-effort: unconditionally undo everything `new` set up, ignoring
+        // errors since there is nothing useful to do with them while unwinding.
+        let _ = execute!(
+            self.stdout,
+            DisableBracketedPaste,
+            terminal::LeaveAlternateScreen,
+            cursor::Show,
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Tokenize `text` into the running `pending` word, sending each completed word
+/// over `tx`. Returns `Err` once the receiver has hung up.
+fn feed_words(text: &str, pending: &mut String, tx: &mpsc::Sender<String>) -> Result<(), ()> {
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !pending.is_empty() && tx.send(std::mem::take(pending)).is_err() {
+                return Err(());
+            }
+        } else {
+            pending.push(ch);
+        }
+    }
+    Ok(())
+}
+
+/// Spawn a thread that incrementally reads stdin, emitting each whitespace-
+/// delimited word over the returned channel as soon as it completes. The channel
+/// closes once stdin reaches EOF, letting the reader start on the first words
+/// while input is still arriving.
+///
+/// Raw bytes are accumulated and only decoded up to the last complete UTF-8
+/// boundary, so a multi-byte grapheme straddling a read boundary is carried
+/// forward intact rather than corrupted into replacement characters.
+fn spawn_stdin_reader() -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buf = [0u8; 4096];
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut pending = String::new();
+        loop {
+            match stdin.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    bytes.extend_from_slice(&buf[..n]);
+                    match std::str::from_utf8(&bytes) {
+                        Ok(text) => {
+                            if feed_words(text, &mut pending, &tx).is_err() {
+                                return;
+                            }
+                            bytes.clear();
+                        }
+                        Err(error) => {
+                            let valid = error.valid_up_to();
+                            // SAFETY: `valid` is the length of a valid prefix.
+                            let text =
+                                unsafe { std::str::from_utf8_unchecked(&bytes[..valid]) };
+                            if feed_words(text, &mut pending, &tx).is_err() {
+                                return;
+                            }
+                            match error.error_len() {
+                                // A genuinely invalid sequence: replace it with
+                                // U+FFFD and drop its bytes so it can't stall
+                                // decoding of everything after.
+                                Some(bad) => {
+                                    pending.push('\u{FFFD}');
+                                    bytes.drain(..valid + bad);
+                                }
+                                // An incomplete tail: keep it for the next read.
+                                None => {
+                                    bytes.drain(..valid);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // Flush any remaining bytes and the trailing word at EOF.
+        if !bytes.is_empty() {
+            let _ = feed_words(&String::from_utf8_lossy(&bytes), &mut pending, &tx);
+        }
+        if !pending.is_empty() {
+            let _ = tx.send(pending);
+        }
+    });
+    rx
+}
+
+/// Flag, for each word, whether it begins a paragraph (is the first word of the
+/// text or the first word after a blank line). The returned vector is aligned with `text.split_whitespace()`.
+fn paragraph_starts(text: &str) -> Vec<bool> {
+    let mut flags = Vec::new();
+    let mut at_paragraph = true;
+    for line in text.split('\n') {
+        if line.trim().is_empty() {
+            at_paragraph = true;
+            continue;
+        }
+        for _ in line.split_whitespace() {
+            flags.push(at_paragraph);
+            at_paragraph = false;
+        }
+    }
+    flags
+}
+
+/// The multiplier applied to the base per-word delay under `--dynamic`: longer
+/// words and trailing punctuation dwell longer, and paragraph starts get an
+/// extra beat.
+fn pacing_factor(word: &str, paragraph_start: bool) -> f64 {
+    let len = word.graphemes(true).count();
+    let length_factor = if len <= 6 {
+        1.0
+    } else if len >= 13 {
+        1.5
+    } else {
+        1.0 + 0.5 * (len - 6) as f64 / 7.0
+    };
+    let punct_factor = match word.chars().next_back() {
+        Some('.') | Some('!') | Some('?') => 2.2,
+        Some(',') | Some(';') | Some(':') => 1.6,
+        _ => 1.0,
+    };
+    let paragraph_factor = if paragraph_start { 1.8 } else { 1.0 };
+    length_factor * punct_factor * paragraph_factor
+}
+
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
     let mut text = String::new();
+    let mut stream_rx: Option<mpsc::Receiver<String>> = None;
     if let Some(ref file_path) = cli.file {
         text = fs::read_to_string(file_path)?;
     } else {
@@ -52,32 +343,114 @@ fn main() -> io::Result<()> {
             }
         }
 
-        // fallback to stdin if clipboard is empty and no file was provided
+        // fall back to stdin if clipboard is empty and no file was provided,
+        // streaming it word by word so slow/growing pipes start immediately
         if text.is_empty() {
-            io::stdin().read_to_string(&mut text)?;
+            stream_rx = Some(spawn_stdin_reader());
         }
     }
 
-    let words: Vec<&str> = text.split_whitespace().collect();
-    if words.is_empty() {
+    let mut words: Vec<String> = text.split_whitespace().map(String::from).collect();
+    if words.is_empty() && stream_rx.is_none() {
         eprintln!(
             "No text to display. Please provide a file, text in clipboard, or pipe text into the program."
         );
         return Ok(());
     }
 
+    let mut paragraph_flags = paragraph_starts(&text);
+    let mut words_since_unpause: u32 = 0;
+
     let mut current_idx = 0;
     let mut wpm = cli.wpm;
+
+    // Only file sources get a stable key; clipboard/stdin are skipped entirely.
+    let mut history = history::History::load();
+    let mut resume_key = cli
+        .file
+        .as_ref()
+        .filter(|_| !cli.no_resume)
+        .and_then(|file| history::source_key(Path::new(file), &text));
+
+    if !cli.reset {
+        if let Some(entry) = resume_key.as_ref().and_then(|key| history.get(key)) {
+            if entry.current_idx < words.len() {
+                current_idx = entry.current_idx;
+            }
+            // Guard against a corrupt/hand-edited history so base_ms never
+            // divides by zero, matching the `d` key's lower bound.
+            wpm = entry.wpm.max(25);
+            println!("resuming at word {}/{}", current_idx + 1, words.len());
+        }
+    }
+
     let mut paused = true;
     let mut stdout = io::stdout();
 
-    terminal::enable_raw_mode()?;
-    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide,)?;
+    let _guard = TerminalGuard::new()?;
+
+    let theme = match cli.theme {
+        ThemeArg::Light => Theme::light(),
+        ThemeArg::Dark => Theme::dark(),
+        // Skip the probe when stdin is being streamed, so the query's stdin read
+        // doesn't steal bytes from the reader thread.
+        ThemeArg::Auto if stream_rx.is_some() => Theme::dark(),
+        ThemeArg::Auto => match query_background_luminance(Duration::from_millis(100)) {
+            Some(luminance) if luminance > 0.5 => Theme::light(),
+            _ => Theme::dark(),
+        },
+    };
+
+    // SIGINT/SIGTERM flip this flag so the main loop breaks and the guard's
+    // Drop runs the same restore path as a normal quit.
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = running.clone();
+        ctrlc::set_handler(move || {
+            running.store(false, Ordering::SeqCst);
+        })
+        .expect("failed to install signal handler");
+    }
 
     let mut last_update = Instant::now();
 
     loop {
-        let delay = Duration::from_millis(60_000 / wpm as u64);
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+
+        // Drain any words the stdin reader has produced since the last tick.
+        if stream_rx.is_some() {
+            let mut disconnected = false;
+            if let Some(rx) = &stream_rx {
+                loop {
+                    match rx.try_recv() {
+                        Ok(word) => words.push(word),
+                        Err(mpsc::TryRecvError::Empty) => break,
+                        Err(mpsc::TryRecvError::Disconnected) => {
+                            disconnected = true;
+                            break;
+                        }
+                    }
+                }
+            }
+            if disconnected {
+                stream_rx = None;
+            }
+        }
+        let streaming = stream_rx.is_some();
+
+        let base_ms = 60_000 / wpm as u64;
+        let delay = if cli.dynamic && current_idx < words.len() {
+            let paragraph = paragraph_flags.get(current_idx).copied().unwrap_or(false);
+            // Slow-start ramp: begin each unpause at ~0.5x target WPM (2x delay)
+            // and ease to full speed over the first five words.
+            let ramp = 2.0 - (words_since_unpause.min(5) as f64) / 5.0;
+            let ms = base_ms as f64 * pacing_factor(&words[current_idx], paragraph) * ramp;
+            Duration::from_millis(ms as u64)
+        } else {
+            Duration::from_millis(base_ms)
+        };
 
         execute!(stdout, terminal::Clear(ClearType::All))?;
 
@@ -86,47 +459,62 @@ fn main() -> io::Result<()> {
         let center_y = rows / 2;
 
         if current_idx < words.len() {
-            let word = words[current_idx];
+            let word = &words[current_idx];
+            // Split into grapheme clusters so combining marks, wide glyphs and
+            // emoji are treated as single visible units rather than bytes.
+            let graphemes: Vec<&str> = word.graphemes(true).collect();
             let orp_idx = if cli.focus {
-                get_orp_index(word.len())
+                get_orp_index(graphemes.len())
             } else {
                 usize::MAX
             };
 
-            // display at the center of the screen
-            let x_start = if cli.focus && orp_idx < word.len() {
-                center_x.saturating_sub(orp_idx as u16)
+            // display at the center of the screen, measuring offsets in display
+            // columns so double-width glyphs stay centered on the ORP
+            let x_start = if cli.focus && orp_idx < graphemes.len() {
+                let width_before: usize = graphemes[..orp_idx]
+                    .iter()
+                    .map(|g| g.width())
+                    .sum();
+                center_x.saturating_sub(width_before as u16)
             } else {
-                center_x.saturating_sub((word.len() / 2) as u16)
+                center_x.saturating_sub((word.width() / 2) as u16)
             };
 
             execute!(stdout, cursor::MoveTo(x_start, center_y))?;
 
-            for (i, c) in word.chars().enumerate() {
+            for (i, g) in graphemes.iter().enumerate() {
                 if cli.focus && i == orp_idx {
                     execute!(
                         stdout,
-                        style::PrintStyledContent(c.to_string().with(Color::Red))
+                        style::PrintStyledContent((*g).with(theme.orp))
                     )?;
                 } else {
-                    execute!(stdout, style::Print(c))?;
+                    execute!(
+                        stdout,
+                        style::PrintStyledContent((*g).with(theme.text))
+                    )?;
                 }
             }
 
-            if cli.focus && orp_idx < word.len() {
+            if cli.focus && orp_idx < graphemes.len() {
                 execute!(
                     stdout,
                     cursor::MoveTo(center_x, center_y + 1),
-                    style::PrintStyledContent("^".with(Color::Red))
+                    style::PrintStyledContent("^".with(theme.orp))
                 )?;
             }
 
             // display WPM and status
+            let count = if streaming {
+                format!("{}/{} (streaming…)", current_idx + 1, words.len())
+            } else {
+                format!("{}/{}", current_idx + 1, words.len())
+            };
             let status_line = format!(
-                "WPM: {} | Word: {}/{} | Status: {} | [Space] Toggle [u/d] WPM [n/p] Prev/Next [q] Quit",
+                "WPM: {} | Word: {} | Status: {} | [Space] Toggle [u/d] WPM [n/p] Prev/Next [q] Quit",
                 wpm,
-                current_idx + 1,
-                words.len(),
+                count,
                 if paused { "Paused" } else { "Playing" }
             );
             execute!(
@@ -134,6 +522,12 @@ fn main() -> io::Result<()> {
                 cursor::MoveTo(0, rows - 1),
                 style::Print(status_line)
             )?;
+        } else if streaming {
+            execute!(
+                stdout,
+                cursor::MoveTo(center_x.saturating_sub(7), center_y),
+                style::Print("streaming…")
+            )?;
         } else {
             execute!(
                 stdout,
@@ -145,58 +539,108 @@ fn main() -> io::Result<()> {
         stdout.flush()?;
 
         // wait for event or timeout
-        let poll_duration = if paused {
+        let caught_up = current_idx + 1 >= words.len();
+        let poll_duration = if paused || (streaming && caught_up) {
             Duration::from_millis(100)
         } else {
             delay.saturating_sub(last_update.elapsed())
         };
 
         if event::poll(poll_duration)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Release {
+            match event::read()? {
+                Event::Resize(..) => {
+                    // Geometry changed; the top of the loop recomputes
+                    // center_x/center_y and repaints from scratch.
                     continue;
                 }
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Char(' ') => {
-                        paused = !paused;
-                        last_update = Instant::now();
-                    }
-                    KeyCode::Char('n') => {
-                        if current_idx + 1 < words.len() {
-                            current_idx += 1;
-                        }
+                Event::Paste(data) => {
+                    // Swap in the freshly pasted text and start over, turning
+                    // the reader into a persistent window fed from the clipboard.
+                    let new_words: Vec<String> =
+                        data.split_whitespace().map(String::from).collect();
+                    if !new_words.is_empty() {
+                        words = new_words;
+                        paragraph_flags = paragraph_starts(&data);
+                        // Pasted text has no stable key; don't save its
+                        // position under the original file's entry.
+                        resume_key = None;
+                        current_idx = 0;
+                        words_since_unpause = 0;
+                        paused = true;
                         last_update = Instant::now();
                     }
-                    KeyCode::Char('p') => {
-                        current_idx = current_idx.saturating_sub(1);
-                        last_update = Instant::now();
+                    continue;
+                }
+                Event::Key(key) => {
+                    if key.kind == KeyEventKind::Release {
+                        continue;
                     }
-                    KeyCode::Char('u') => {
-                        wpm = wpm.saturating_add(25);
+                    // Raw mode disables ISIG, so interactive Ctrl-C arrives as a
+                    // key event rather than a SIGINT; route it to the same quit
+                    // path as `q` and externally-delivered signals.
+                    if key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        break;
                     }
-                    KeyCode::Char('d') => {
-                        wpm = wpm.saturating_sub(25).max(25);
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Char(' ') => {
+                            paused = !paused;
+                            if !paused {
+                                words_since_unpause = 0;
+                            }
+                            last_update = Instant::now();
+                        }
+                        KeyCode::Char('n') => {
+                            if current_idx + 1 < words.len() {
+                                current_idx += 1;
+                            }
+                            last_update = Instant::now();
+                        }
+                        KeyCode::Char('p') => {
+                            current_idx = current_idx.saturating_sub(1);
+                            last_update = Instant::now();
+                        }
+                        KeyCode::Char('u') => {
+                            wpm = wpm.saturating_add(25);
+                        }
+                        KeyCode::Char('d') => {
+                            wpm = wpm.saturating_sub(25).max(25);
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
 
         if !paused && last_update.elapsed() >= delay {
             if current_idx + 1 < words.len() {
                 current_idx += 1;
+                words_since_unpause = words_since_unpause.saturating_add(1);
                 last_update = Instant::now();
-            } else {
+            } else if !streaming {
                 paused = true;
                 current_idx = words.len();
             }
+            // If still streaming, hold at the last word and wait for more.
         }
     }
 
-    // restore terminal
-    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
-    terminal::disable_raw_mode()?;
+    // Terminal restoration happens in TerminalGuard::drop.
+    drop(_guard);
+
+    if let Some(key) = resume_key {
+        history.record(
+            key,
+            history::Entry {
+                current_idx: current_idx.min(words.len()),
+                wpm,
+            },
+        );
+        history.save()?;
+    }
 
     Ok(())
 }